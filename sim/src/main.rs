@@ -4,7 +4,7 @@ use std::collections::BTreeMap;
 use std::iter;
 use std::panic::{self, AssertUnwindSafe};
 
-use interlog_core::{ExternalMemory, Storage, StorageOffset};
+use interlog_core::{ExternalMemory, ReadErr, Storage, StorageOffset};
 
 const MAX_SIM_TIME_MS: u64 = 1000 * 60 * 60; // One hour
 
@@ -62,8 +62,50 @@ impl<'a> Storage for AppendOnlyMemory<'a> {
 		self.0.extend_from_slice_unchecked(data);
 	}
 
-	fn read(&self, buf: &mut [u8], offset: usize) {
-		buf.copy_from_slice(&self.0[offset..offset + buf.len()])
+	fn read_exact(&self, buf: &mut [u8], offset: usize) -> Result<(), ReadErr> {
+		let available = self.used().saturating_sub(offset);
+		if buf.len() > available {
+			return Err(ReadErr::UnexpectedEof {
+				requested: buf.len(),
+				available
+			});
+		}
+
+		buf.copy_from_slice(&self.0[offset..offset + buf.len()]);
+		Ok(())
+	}
+}
+
+/// Walks the length-prefixed events of a `Storage` sequentially, from the
+/// start, stopping cleanly at the first header or payload that runs past
+/// what's been committed instead of panicking. This is the safe,
+/// resumable way to scan a replica that may have a torn trailing write.
+struct LogCursor<'a, S: Storage> {
+	storage: &'a S,
+	offset: usize
+}
+
+impl<'a, S: Storage> LogCursor<'a, S> {
+	fn new(storage: &'a S) -> Self {
+		Self { storage, offset: 0 }
+	}
+}
+
+impl<'a, S: Storage> Iterator for LogCursor<'a, S> {
+	type Item = Vec<u8>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut header = [0u8; 4];
+		self.storage.read_exact(&mut header, self.offset).ok()?;
+		let payload_len = u32::from_le_bytes(header) as usize;
+
+		let mut payload = vec![0u8; payload_len];
+		self.storage
+			.read_exact(&mut payload, self.offset + header.len())
+			.ok()?;
+
+		self.offset += header.len() + payload_len;
+		Some(payload)
 	}
 }
 
@@ -250,3 +292,26 @@ fn main() {
 		ctx.stats.total_commits as f64 / (MAX_SIM_TIME_MS as f64 / 1000.0),
 	);
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn log_cursor_stops_cleanly_at_torn_write() {
+		let mut buf = [0u8; 64];
+		let mut storage = AppendOnlyMemory::new(&mut buf);
+
+		let first = b"hello";
+		storage.append(&(first.len() as u32).to_le_bytes());
+		storage.append(first);
+
+		// A torn write: the header claims more payload than was ever
+		// written, as if the process died mid-append
+		storage.append(&100u32.to_le_bytes());
+		storage.append(b"oops");
+
+		let events: Vec<Vec<u8>> = LogCursor::new(&storage).collect();
+		assert_eq!(events, vec![first.to_vec()]);
+	}
+}
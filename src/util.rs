@@ -1,13 +1,13 @@
 //! Fixed capacity data structures, that do not allocate when modified.
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
 use std::slice::SliceIndex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-fn uninit_boxed_slice<T>(size: usize) -> Box<[T]> {
-	let mut result = Vec::with_capacity(size);
-	#[allow(clippy::uninit_vec)]
-	unsafe {
-		result.set_len(size)
-	};
-	result.into_boxed_slice()
+/// An array of `N` uninitialized elements. Safe because `MaybeUninit<T>`
+/// carries no validity invariant, so the array itself needs no init.
+const fn uninit_array<T, const N: usize>() -> [MaybeUninit<T>; N] {
+	unsafe { MaybeUninit::uninit().assume_init() }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -50,8 +50,8 @@ pub trait Segmentable<T> {
 
 /// Fixed Capacity Vector
 /// Tigerstyle: There IS a limit
-pub struct FixVec<T> {
-	elems: alloc::boxed::Box<[T]>,
+pub struct FixVec<T, const N: usize> {
+	elems: [MaybeUninit<T>; N],
 	len: usize
 }
 
@@ -59,17 +59,14 @@ pub struct FixVec<T> {
 pub struct FixVecOverflow;
 pub type FixVecRes = Result<(), FixVecOverflow>;
 
-impl<T> FixVec<T> {
-	#[allow(clippy::uninit_vec)]
-	pub fn new(capacity: usize) -> FixVec<T> {
-		let elems = uninit_boxed_slice(capacity);
-		assert_eq!(std::mem::size_of_val(&elems), 16);
-		Self { elems, len: 0 }
+impl<T, const N: usize> FixVec<T, N> {
+	pub const fn new() -> Self {
+		Self { elems: uninit_array(), len: 0 }
 	}
 
 	#[inline]
-	pub fn capacity(&self) -> usize {
-		self.elems.len()
+	pub const fn capacity(&self) -> usize {
+		N
 	}
 
 	#[inline]
@@ -86,10 +83,28 @@ impl<T> FixVec<T> {
 		(self.capacity() >= new_len).then_some(()).ok_or(FixVecOverflow)
 	}
 
+	/// The initialized prefix, as a plain slice
+	fn as_slice(&self) -> &[T] {
+		// Safe: elems[..len] is always initialized, and MaybeUninit<T> has
+		// the same layout as T
+		unsafe {
+			core::slice::from_raw_parts(self.elems.as_ptr().cast(), self.len)
+		}
+	}
+
+	fn as_mut_slice(&mut self) -> &mut [T] {
+		unsafe {
+			core::slice::from_raw_parts_mut(
+				self.elems.as_mut_ptr().cast(),
+				self.len
+			)
+		}
+	}
+
 	pub fn push(&mut self, value: T) -> FixVecRes {
 		let new_len = self.len + 1;
 		self.check_capacity(new_len)?;
-		self.elems[self.len] = value;
+		self.elems[self.len].write(value);
 		self.len = new_len;
 		Ok(())
 	}
@@ -104,7 +119,7 @@ impl<T> FixVec<T> {
 
 	fn insert(&mut self, index: usize, element: T) -> FixVecRes {
 		self.check_capacity(index + 1)?;
-		self.elems[index] = element;
+		self.elems[index].write(element);
 		Ok(())
 	}
 
@@ -112,16 +127,18 @@ impl<T> FixVec<T> {
 	where
 		I: SliceIndex<[T]>
 	{
-		self.elems[..self.len].get(index)
+		self.as_slice().get(index)
 	}
 }
 
-impl<T: Clone + core::fmt::Debug> FixVec<T> {
+impl<T: Clone + core::fmt::Debug, const N: usize> FixVec<T, N> {
 	pub fn resize(&mut self, new_len: usize, value: T) -> FixVecRes {
 		self.check_capacity(new_len)?;
 
 		if new_len > self.len {
-			self.elems[self.len..new_len].fill(value);
+			for slot in &mut self.elems[self.len..new_len] {
+				slot.write(value.clone());
+			}
 		}
 
 		self.len = new_len;
@@ -130,56 +147,62 @@ impl<T: Clone + core::fmt::Debug> FixVec<T> {
 	}
 }
 
-impl<T: Copy> FixVec<T> {
+impl<T: Copy, const N: usize> FixVec<T, N> {
 	pub fn extend_from_slice(&mut self, other: &[T]) -> FixVecRes {
 		let new_len = self.len + other.len();
 		self.check_capacity(new_len)?;
-		self.elems[self.len..new_len].copy_from_slice(other);
+		// Safe: other is `T: Copy`, and `[self.len..new_len]` is in bounds
+		unsafe {
+			core::ptr::copy_nonoverlapping(
+				other.as_ptr(),
+				self.elems[self.len..new_len].as_mut_ptr().cast(),
+				other.len()
+			);
+		}
 		self.len = new_len;
 		Ok(())
 	}
 }
 
-impl<T> std::ops::Deref for FixVec<T> {
+impl<T, const N: usize> std::ops::Deref for FixVec<T, N> {
 	type Target = [T];
 
 	fn deref(&self) -> &Self::Target {
-		&self.elems[..self.len]
+		self.as_slice()
 	}
 }
 
-impl<T> std::ops::DerefMut for FixVec<T> {
+impl<T, const N: usize> std::ops::DerefMut for FixVec<T, N> {
 	fn deref_mut(&mut self) -> &mut Self::Target {
-		&mut self.elems[..self.len]
+		self.as_mut_slice()
 	}
 }
 
-impl<T> Segmentable<T> for FixVec<T> {
+impl<T, const N: usize> Segmentable<T> for FixVec<T, N> {
 	fn segment(&self, index: &Segment) -> Option<&[T]> {
-		self.elems[..self.len].get(index.range())
+		self.as_slice().get(index.range())
 	}
 }
 
+/// `slice` is larger than the buffer's capacity, so it can't be written
+/// even after wrapping.
 #[derive(Debug)]
 pub struct CircBufWrapAround;
 
-pub struct CircBuf<T> {
-	buf: Box<[T]>,
+pub struct CircBuf<T, const N: usize> {
+	buf: [MaybeUninit<T>; N],
 	len: usize,
 	write_idx: usize
 }
 
-impl<T> CircBuf<T> {
-	pub fn new(capacity: usize) -> Self {
-		let buffer = uninit_boxed_slice(capacity);
-		let len = 0;
-		let write_idx = 0;
-		Self { buf: buffer, len, write_idx }
+impl<T, const N: usize> CircBuf<T, N> {
+	pub const fn new() -> Self {
+		Self { buf: uninit_array(), len: 0, write_idx: 0 }
 	}
 
 	#[inline]
-	pub fn capacity(&self) -> usize {
-		self.buf.len()
+	pub const fn capacity(&self) -> usize {
+		N
 	}
 
 	#[inline]
@@ -188,7 +211,7 @@ impl<T> CircBuf<T> {
 	}
 
 	pub fn push(&mut self, item: T) {
-		self.buf[self.write_idx] = item;
+		self.buf[self.write_idx].write(item);
 		self.write_idx = (self.write_idx + 1) % self.capacity();
 		self.len = core::cmp::min(self.len + 1, self.capacity());
 	}
@@ -198,39 +221,81 @@ impl<T> CircBuf<T> {
 			return None;
 		}
 		let index = (index + self.write_idx).wrapping_rem_euclid(self.len);
-		(self.len > index).then(|| &self.buf[index])
+		// Safe: index < self.len, and everything below self.len is init
+		(self.len > index).then(|| unsafe { self.buf[index].assume_init_ref() })
+	}
+
+	/// Safe: `range` must be within `N` and already initialized.
+	fn raw_slice(&self, range: core::ops::Range<usize>) -> &[T] {
+		let ptr = self.buf[range.clone()].as_ptr().cast();
+		unsafe { core::slice::from_raw_parts(ptr, range.len()) }
+	}
+
+	/// The up-to-two contiguous segments backing the buffer, in logical
+	/// (oldest to newest) order, for vectored reads that don't want to
+	/// walk the ring element-by-element.
+	pub fn segments(&self) -> (&[T], &[T]) {
+		if self.len < self.capacity() {
+			// Hasn't wrapped yet: everything is one contiguous run from 0
+			(self.raw_slice(0..self.len), &[])
+		} else {
+			(
+				self.raw_slice(self.write_idx..self.capacity()),
+				self.raw_slice(0..self.write_idx)
+			)
+		}
 	}
 }
 
-impl<T: Copy> CircBuf<T> {
-	// Will fail if it causes a wrap around
-	// slice should remain contiguous in memory
-	// TODO: this should just start writing from the beginning if it wraps around?
+impl<T: Copy, const N: usize> CircBuf<T, N> {
+	/// Bulk write that wraps at the capacity boundary instead of failing:
+	/// copies `min(slice.len(), capacity - write_idx)` elements starting
+	/// at `write_idx`, then the remainder starting at index 0, the same
+	/// way `push` would one element at a time.
 	pub fn extend_from_slice(
 		&mut self,
 		slice: &[T]
 	) -> Result<(), CircBufWrapAround> {
-		let contiguous_space_left = self.capacity() - self.write_idx;
-		if contiguous_space_left > slice.len() {
-			self.buf[..self.len].copy_from_slice(slice);
+		if slice.len() > self.capacity() {
+			return Err(CircBufWrapAround);
+		}
+
+		let first_len = core::cmp::min(slice.len(), self.capacity() - self.write_idx);
+		let (first, second) = slice.split_at(first_len);
+
+		unsafe {
+			core::ptr::copy_nonoverlapping(
+				first.as_ptr(),
+				self.buf[self.write_idx..self.write_idx + first.len()]
+					.as_mut_ptr()
+					.cast(),
+				first.len()
+			);
+			core::ptr::copy_nonoverlapping(
+				second.as_ptr(),
+				self.buf[..second.len()].as_mut_ptr().cast(),
+				second.len()
+			);
 		}
 
-		Err(CircBufWrapAround)
+		self.write_idx = (self.write_idx + slice.len()) % self.capacity();
+		self.len = core::cmp::min(self.len + slice.len(), self.capacity());
+		Ok(())
 	}
 }
 
-impl<T> CircBuf<T> {
-	fn iter(&self) -> CircBufIterator<'_, T> {
+impl<T, const N: usize> CircBuf<T, N> {
+	fn iter(&self) -> CircBufIterator<'_, T, N> {
 		CircBufIterator { circ_buf: self, index: 0 }
 	}
 }
 
-struct CircBufIterator<'a, T> {
-	circ_buf: &'a CircBuf<T>,
+struct CircBufIterator<'a, T, const N: usize> {
+	circ_buf: &'a CircBuf<T, N>,
 	index: usize
 }
 
-impl<'a, T> Iterator for CircBufIterator<'a, T> {
+impl<'a, T, const N: usize> Iterator for CircBufIterator<'a, T, N> {
 	type Item = &'a T;
 
 	fn next(&mut self) -> Option<Self::Item> {
@@ -246,8 +311,8 @@ impl<'a, T> Iterator for CircBufIterator<'a, T> {
 }
 
 /// Implementation of Simon Cookess bi-partite circular buffer
-pub struct BipBuf<T> {
-	buf: Box<[T]>,
+pub struct BipBuf<T, const N: usize> {
+	buf: [MaybeUninit<T>; N],
 	a_start: usize,
 	a_end: usize,
 	b_start: usize,
@@ -256,10 +321,10 @@ pub struct BipBuf<T> {
 	reserve_end: usize
 }
 
-impl<T> BipBuf<T> {
-	pub fn new(capacity: usize) -> Self {
+impl<T, const N: usize> BipBuf<T, N> {
+	pub const fn new() -> Self {
 		Self {
-			buf: uninit_boxed_slice(capacity),
+			buf: uninit_array(),
 			a_start: 0,
 			a_end: 0,
 			b_start: 0,
@@ -277,6 +342,417 @@ impl<T> BipBuf<T> {
 		self.reserve_start = 0;
 		self.reserve_end = 0;
 	}
+
+	#[inline]
+	pub const fn capacity(&self) -> usize {
+		N
+	}
+
+	/// The largest contiguous free run, as (start, len)
+	fn free_gap(&self) -> (usize, usize) {
+		if self.b_end > 0 {
+			// B is active, so the only free space left is between B and A
+			(self.b_end, self.a_start - self.b_end)
+		} else {
+			let trailing = self.capacity() - self.a_end;
+			let leading = self.a_start;
+			if trailing >= leading {
+				(self.a_end, trailing)
+			} else {
+				(0, leading)
+			}
+		}
+	}
+
+	/// Reserve up to `len` elements of contiguous, writable space. Returns
+	/// the largest contiguous free run available, which may be shorter than
+	/// `len`, or `None` if there's no room for even one element.
+	///
+	/// The reservation must be turned into an in-use region with `commit`
+	/// before another `reserve` can be made.
+	pub fn reserve(&mut self, len: usize) -> Option<&mut [T]> {
+		let (start, available) = self.free_gap();
+
+		if available == 0 {
+			return None;
+		}
+
+		let len = core::cmp::min(len, available);
+		self.reserve_start = start;
+		self.reserve_end = start + len;
+		// Safe: start..start+len is within N, and reserved memory is only
+		// ever written through before being read via `peek`
+		let ptr = self.buf[self.reserve_start..self.reserve_end]
+			.as_mut_ptr()
+			.cast();
+		Some(unsafe { core::slice::from_raw_parts_mut(ptr, len) })
+	}
+
+	/// Promote the first `len` elements of the outstanding reservation into
+	/// an in-use region.
+	pub fn commit(&mut self, len: usize) {
+		assert!(
+			len <= self.reserve_end - self.reserve_start,
+			"commit len must not exceed the outstanding reservation"
+		);
+
+		if self.reserve_start == self.a_end {
+			self.a_end += len;
+		} else {
+			self.b_end = self.reserve_start + len;
+		}
+
+		self.reserve_start = 0;
+		self.reserve_end = 0;
+	}
+
+	/// The contiguous readable region. Always region A: B only becomes
+	/// readable once `decommit` empties A and promotes B in its place.
+	pub fn peek(&self) -> &[T] {
+		// Safe: a_start..a_end is always a committed, initialized region
+		let ptr = self.buf[self.a_start..self.a_end].as_ptr().cast();
+		unsafe { core::slice::from_raw_parts(ptr, self.a_end - self.a_start) }
+	}
+
+	/// Advance the read cursor past the first `len` elements of `peek()`.
+	/// Once A is fully read, B (if any) is promoted to A.
+	pub fn decommit(&mut self, len: usize) {
+		assert!(
+			len <= self.a_end - self.a_start,
+			"decommit len must not exceed the readable region"
+		);
+
+		self.a_start += len;
+
+		if self.a_start == self.a_end {
+			self.a_start = 0;
+			self.a_end = self.b_end;
+			self.b_end = 0;
+		}
+	}
+
+	/// Region B: the newer data that wrapped past A, if any.
+	fn peek_b(&self) -> &[T] {
+		// Safe: b_start..b_end (b_start is always 0) is always committed
+		let ptr = self.buf[self.b_start..self.b_end].as_ptr().cast();
+		unsafe { core::slice::from_raw_parts(ptr, self.b_end - self.b_start) }
+	}
+}
+
+impl<const N: usize> BipBuf<u8, N> {
+	/// Present A then B as a single `Buf`, for reads that straddle the
+	/// wrap point without an intermediate copy.
+	pub fn as_buf(&self) -> Chain<&[u8], &[u8]> {
+		Chain::new(self.peek(), self.peek_b())
+	}
+}
+
+impl<const N: usize> CircBuf<u8, N> {
+	/// Present both segments as a single `Buf`, for reads that straddle
+	/// the wrap point without an intermediate copy.
+	pub fn as_buf(&self) -> Chain<&[u8], &[u8]> {
+		let (older, newer) = self.segments();
+		Chain::new(older, newer)
+	}
+}
+
+/// A readable sequence of bytes, yielded one contiguous chunk at a time.
+/// Modelled on `bytes::Buf`, so a header and a payload can be appended in
+/// one call via `header.chain(payload)` instead of staging them into a
+/// single contiguous buffer first.
+pub trait Buf {
+	fn remaining(&self) -> usize;
+
+	/// The next contiguous chunk of unread bytes. Never empty unless
+	/// `remaining() == 0`.
+	fn chunk(&self) -> &[u8];
+
+	/// Mark the first `cnt` bytes of `chunk()` as read.
+	fn advance(&mut self, cnt: usize);
+
+	/// Present `self` followed by `next` as one logical sequence.
+	fn chain<B: Buf>(self, next: B) -> Chain<Self, B>
+	where
+		Self: Sized
+	{
+		Chain::new(self, next)
+	}
+}
+
+/// A writable sequence of byte slots, filled one contiguous chunk at a
+/// time. Modelled on `bytes::BufMut`.
+pub trait BufMut {
+	fn remaining_mut(&self) -> usize;
+
+	/// The next contiguous chunk of unwritten slots. Never empty unless
+	/// `remaining_mut() == 0`.
+	fn chunk_mut(&mut self) -> &mut [u8];
+
+	/// Mark the first `cnt` bytes of `chunk_mut()` as written. Callers
+	/// must have actually initialized those bytes first.
+	unsafe fn advance_mut(&mut self, cnt: usize);
+}
+
+impl Buf for &[u8] {
+	fn remaining(&self) -> usize {
+		self.len()
+	}
+
+	fn chunk(&self) -> &[u8] {
+		self
+	}
+
+	fn advance(&mut self, cnt: usize) {
+		*self = &self[cnt..];
+	}
+}
+
+impl BufMut for &mut [u8] {
+	fn remaining_mut(&self) -> usize {
+		self.len()
+	}
+
+	fn chunk_mut(&mut self) -> &mut [u8] {
+		self
+	}
+
+	unsafe fn advance_mut(&mut self, cnt: usize) {
+		let slice = core::mem::take(self);
+		*self = &mut slice[cnt..];
+	}
+}
+
+/// Presents two buffers, `a` then `b`, as a single logical sequence:
+/// yields `a`'s chunks until it's exhausted, then `b`'s.
+pub struct Chain<A, B> {
+	a: A,
+	b: B
+}
+
+impl<A, B> Chain<A, B> {
+	pub fn new(a: A, b: B) -> Self {
+		Self { a, b }
+	}
+}
+
+impl<A: Buf, B: Buf> Buf for Chain<A, B> {
+	fn remaining(&self) -> usize {
+		self.a.remaining() + self.b.remaining()
+	}
+
+	fn chunk(&self) -> &[u8] {
+		if self.a.remaining() > 0 {
+			self.a.chunk()
+		} else {
+			self.b.chunk()
+		}
+	}
+
+	fn advance(&mut self, cnt: usize) {
+		let a_remaining = self.a.remaining();
+		if cnt <= a_remaining {
+			self.a.advance(cnt);
+		} else {
+			self.a.advance(a_remaining);
+			self.b.advance(cnt - a_remaining);
+		}
+	}
+}
+
+/// A read past what has actually been written.
+#[derive(Debug)]
+pub enum ReadErr {
+	UnexpectedEof { requested: usize, available: usize }
+}
+
+/// A byte-addressable append-only store. `append`/`read_exact` are the minimal
+/// primitives an implementor provides; `append_vectored`/`read_buf` are
+/// built on top so callers never need to stage a contiguous copy first.
+pub trait Storage {
+	fn append(&mut self, data: &[u8]);
+
+	/// Fill `buf` from `offset`, or fail with `ReadErr::UnexpectedEof` if
+	/// the request runs past what's been committed, instead of panicking.
+	/// This is what lets a reader (replication, crash recovery) request a
+	/// range it doesn't yet know the end of.
+	fn read_exact(&self, buf: &mut [u8], offset: usize) -> Result<(), ReadErr>;
+
+	/// Append every chunk of `bufs` in order, e.g. `header.chain(payload)`.
+	fn append_vectored(&mut self, bufs: &mut dyn Buf) {
+		while bufs.remaining() > 0 {
+			let len = bufs.chunk().len();
+			self.append(bufs.chunk());
+			bufs.advance(len);
+		}
+	}
+
+	/// Slice-of-slices convenience: appends each slice in order, the same
+	/// bytes `append_vectored` would write for them.
+	fn append_all(&mut self, bufs: &[&[u8]]) {
+		for buf in bufs {
+			self.append(buf);
+		}
+	}
+
+	/// Fill every chunk of `buf`, reading from `offset` onward.
+	fn read_buf(
+		&self,
+		buf: &mut dyn BufMut,
+		mut offset: usize
+	) -> Result<(), ReadErr> {
+		while buf.remaining_mut() > 0 {
+			let len = buf.chunk_mut().len();
+			self.read_exact(buf.chunk_mut(), offset)?;
+			// Safe: read_exact just filled exactly `len` bytes
+			unsafe { buf.advance_mut(len) };
+			offset += len;
+		}
+		Ok(())
+	}
+}
+
+/// Sentinel `next`/index value meaning "no slot", packed into the lower
+/// 32 bits of the head word.
+const NIL: u32 = u32::MAX;
+
+struct Slot<T> {
+	value: T,
+	next: u32
+}
+
+/// A lock-free pool of `N` pre-allocated `T`s, usable from multiple
+/// threads without locks. Backed by a Treiber stack over an intrusive
+/// free list: each free slot stores the index of the next free slot, and
+/// the head is a single `AtomicUsize`.
+///
+/// To defeat the ABA problem on the pop side, the head word packs a
+/// monotonically-incrementing tag into its upper 32 bits alongside the
+/// free-list index in the lower 32 bits, and every compare-exchange
+/// includes the tag, so a slot recycled between load and CAS forces a
+/// retry rather than corrupting the list.
+pub struct Pool<T, const N: usize> {
+	slots: [UnsafeCell<Slot<T>>; N],
+	head: AtomicUsize
+}
+
+// Safe: access to a slot is only ever granted to the single `Guard` that
+// currently owns it, which the Treiber stack enforces.
+unsafe impl<T: Send, const N: usize> Sync for Pool<T, N> {}
+
+impl<T, const N: usize> Pool<T, N> {
+	pub fn new() -> Self
+	where
+		T: Default
+	{
+		assert!(N < NIL as usize, "pool capacity must fit in 32 bits");
+
+		let slots = core::array::from_fn(|i| {
+			let next = if i + 1 < N { (i + 1) as u32 } else { NIL };
+			UnsafeCell::new(Slot { value: T::default(), next })
+		});
+
+		let head_index = if N == 0 { NIL } else { 0 };
+		Self { slots, head: AtomicUsize::new(Self::pack(0, head_index)) }
+	}
+
+	#[inline]
+	pub const fn capacity(&self) -> usize {
+		N
+	}
+
+	fn pack(tag: u32, index: u32) -> usize {
+		((tag as usize) << u32::BITS) | index as usize
+	}
+
+	fn unpack(word: usize) -> (u32, u32) {
+		((word >> u32::BITS) as u32, word as u32)
+	}
+
+	/// Pop the head of the free list, or `None` if the pool is exhausted.
+	pub fn try_alloc(&self) -> Option<Guard<'_, T, N>> {
+		let mut current = self.head.load(Ordering::Acquire);
+
+		loop {
+			let (tag, index) = Self::unpack(current);
+			if index == NIL {
+				return None;
+			}
+
+			// Safe: `index` is only reachable from the free list, and a
+			// slot on the free list has no live Guard
+			let next = unsafe { (*self.slots[index as usize].get()).next };
+			let new_head = Self::pack(tag.wrapping_add(1), next);
+
+			match self.head.compare_exchange_weak(
+				current,
+				new_head,
+				Ordering::AcqRel,
+				Ordering::Acquire
+			) {
+				Ok(_) => return Some(Guard { pool: self, index }),
+				Err(actual) => current = actual
+			}
+		}
+	}
+
+	/// Push `index` back onto the head of the free list.
+	fn free(&self, index: u32) {
+		let mut current = self.head.load(Ordering::Acquire);
+
+		loop {
+			let (tag, head_index) = Self::unpack(current);
+			// Safe: this slot's Guard is being dropped, so we have
+			// exclusive access to it again
+			unsafe { (*self.slots[index as usize].get()).next = head_index };
+			let new_head = Self::pack(tag.wrapping_add(1), index);
+
+			match self.head.compare_exchange_weak(
+				current,
+				new_head,
+				Ordering::AcqRel,
+				Ordering::Acquire
+			) {
+				Ok(_) => return,
+				Err(actual) => current = actual
+			}
+		}
+	}
+}
+
+/// A handle to a slot allocated from a `Pool`, returned to the pool when
+/// dropped.
+pub struct Guard<'a, T, const N: usize> {
+	pool: &'a Pool<T, N>,
+	index: u32
+}
+
+// Safe: a `Guard` behaves like an owning `Box<T>` over its slot — sharing
+// `&Guard<T, N>` across threads only ever gives out `&T`, so it's Sync
+// iff `T` is, same as `Box<T>`. Without this explicit bound, auto-trait
+// derivation would make `Guard` Sync for any `T: Send` (its only other
+// field is `&Pool<T, N>`, Sync whenever `Pool<T, N>` is), letting two
+// threads race `&Cell<i32>` into the same slot even though `Cell` is
+// `!Sync`.
+unsafe impl<'a, T: Sync, const N: usize> Sync for Guard<'a, T, N> {}
+
+impl<'a, T, const N: usize> core::ops::Deref for Guard<'a, T, N> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		unsafe { &(*self.pool.slots[self.index as usize].get()).value }
+	}
+}
+
+impl<'a, T, const N: usize> core::ops::DerefMut for Guard<'a, T, N> {
+	fn deref_mut(&mut self) -> &mut T {
+		unsafe { &mut (*self.pool.slots[self.index as usize].get()).value }
+	}
+}
+
+impl<'a, T, const N: usize> Drop for Guard<'a, T, N> {
+	fn drop(&mut self) {
+		self.pool.free(self.index);
+	}
 }
 
 #[cfg(test)]
@@ -286,7 +762,7 @@ mod tests {
 
 	#[test]
 	fn circ_buf() {
-		let mut cb = CircBuf::new(4);
+		let mut cb: CircBuf<char, 4> = CircBuf::new();
 
 		// Preconditions
 		assert_eq!(cb.iter().collect::<String>(), "");
@@ -323,4 +799,128 @@ mod tests {
 		assert_eq!(cb.write_idx, 2);
 		assert_eq!(cb.len, 4);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn fix_vec_resize() {
+		let mut fv: FixVec<u8, 4> = FixVec::new();
+
+		fv.resize(3, 7).unwrap();
+		assert_eq!(&fv[..], [7, 7, 7]);
+
+		// Shrinking must not panic, and must not touch the retained prefix
+		fv.resize(1, 9).unwrap();
+		assert_eq!(&fv[..], [7]);
+
+		// Growing back fills only the newly exposed slots
+		fv.resize(3, 5).unwrap();
+		assert_eq!(&fv[..], [7, 5, 5]);
+
+		assert!(fv.resize(5, 0).is_err());
+	}
+
+	#[test]
+	fn bip_buf() {
+		let mut bb: BipBuf<u8, 4> = BipBuf::new();
+
+		let r = bb.reserve(3).unwrap();
+		assert_eq!(r.len(), 3);
+		r.copy_from_slice(b"abc");
+		bb.commit(3);
+		assert_eq!(bb.peek(), b"abc");
+
+		// Decommitting "ab" leaves one trailing byte free but two leading,
+		// so reserving more starts B instead of extending A
+		bb.decommit(2);
+		assert_eq!(bb.peek(), b"c");
+		bb.reserve(2).unwrap()[..1].copy_from_slice(b"d");
+		bb.commit(1);
+		assert_eq!(bb.peek(), b"c");
+
+		// Freeing the rest of A makes room again, and promotes B to A
+		bb.decommit(1);
+		assert_eq!(bb.peek(), b"d");
+	}
+
+	#[test]
+	fn circ_buf_extend_from_slice() {
+		// Exactly fills
+		let mut cb: CircBuf<u8, 4> = CircBuf::new();
+		cb.extend_from_slice(b"abcd").unwrap();
+		assert_eq!(cb.segments(), (&b"abcd"[..], &b""[..]));
+
+		// Partially wraps, overwriting the oldest data ('a', 'b')
+		cb.extend_from_slice(b"ef").unwrap();
+		assert_eq!(cb.segments(), (&b"cd"[..], &b"ef"[..]));
+
+		// Rejected outright: bigger than the whole buffer
+		assert!(cb.extend_from_slice(b"123456").is_err());
+	}
+
+	#[test]
+	fn chain_buf() {
+		let header: &[u8] = b"head:";
+		let payload: &[u8] = b"payload";
+		let mut chained = header.chain(payload);
+
+		assert_eq!(chained.remaining(), header.len() + payload.len());
+		assert_eq!(chained.chunk(), header);
+
+		chained.advance(3);
+		assert_eq!(chained.chunk(), b"d:");
+
+		chained.advance(2);
+		assert_eq!(chained.chunk(), payload);
+		assert_eq!(chained.remaining(), payload.len());
+	}
+
+	#[test]
+	fn bip_buf_as_buf() {
+		let mut bb: BipBuf<u8, 4> = BipBuf::new();
+		bb.reserve(3).unwrap().copy_from_slice(b"abc");
+		bb.commit(3);
+		bb.decommit(2);
+
+		// A is down to one byte, so reserving 2 more starts B
+		bb.reserve(2).unwrap().copy_from_slice(b"de");
+		bb.commit(2);
+
+		let mut buf = bb.as_buf();
+		assert_eq!(buf.remaining(), 3);
+		assert_eq!(buf.chunk(), b"c");
+		buf.advance(1);
+		assert_eq!(buf.chunk(), b"de");
+	}
+
+	// Run with `RUSTFLAGS="-Z sanitizer=thread" cargo +nightly test` to
+	// have TSAN verify there's no data race on the free list.
+	#[test]
+	fn pool_concurrent_alloc_free() {
+		use std::sync::Arc;
+		use std::thread;
+
+		let pool: Arc<Pool<usize, 8>> = Arc::new(Pool::new());
+		let handles: Vec<_> = (0..4)
+			.map(|_| {
+				let pool = Arc::clone(&pool);
+				thread::spawn(move || {
+					for _ in 0..1000 {
+						if let Some(mut guard) = pool.try_alloc() {
+							*guard += 1;
+						}
+					}
+				})
+			})
+			.collect();
+
+		for handle in handles {
+			handle.join().unwrap();
+		}
+
+		// Every slot must have been returned to the free list
+		let mut guards = Vec::new();
+		while let Some(guard) = pool.try_alloc() {
+			guards.push(guard);
+		}
+		assert_eq!(guards.len(), pool.capacity());
+	}
+}